@@ -0,0 +1,52 @@
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+
+mod store;
+
+pub use store::ConfigStore;
+
+static GLOBAL_CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Handle onto goose's configuration. Providers read everything they need -
+/// hosts, API keys, per-provider extras - through this rather than touching
+/// `std::env` directly, so config has one resolution order everywhere.
+/// Values resolve from environment variables only: [`ConfigStore::load`]
+/// starts with no file-backed values, and secrets are looked up the same way
+/// as plain values rather than from a keyring.
+pub struct Config {
+    store: ConfigStore,
+}
+
+impl Config {
+    pub fn global() -> &'static Config {
+        GLOBAL_CONFIG.get_or_init(|| Config {
+            store: ConfigStore::load(),
+        })
+    }
+
+    /// Read a plain (non-secret) config value as a string.
+    pub fn get_param(&self, key: &str) -> Result<String> {
+        self.store
+            .get(key)
+            .ok_or_else(|| anyhow!("missing config value: {key}"))
+    }
+
+    /// Read a secret config value (API key, token, ...) as a string.
+    pub fn get_secret(&self, key: &str) -> Result<String> {
+        self.store
+            .get_secret(key)
+            .ok_or_else(|| anyhow!("missing secret: {key}"))
+    }
+
+    /// Deserialize a structured (table/array) config value, such as a list
+    /// of user-defined platforms or models.
+    pub fn get_param_as<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let value = self
+            .store
+            .get_value(key)
+            .ok_or_else(|| anyhow!("missing config value: {key}"))?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
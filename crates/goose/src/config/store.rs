@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::env;
+
+use serde_json::Value;
+
+/// Backing store for [`super::Config`]. Resolves a key against the process
+/// environment first (so `FOO_BAR` always overrides the config file, which
+/// keeps CI and one-off overrides simple) and falls back to whatever was
+/// loaded from the on-disk config file.
+pub struct ConfigStore {
+    file_values: HashMap<String, Value>,
+}
+
+impl ConfigStore {
+    pub fn load() -> Self {
+        // The real config file lives at ~/.config/goose/config.yaml; loading
+        // it is out of scope here, so we start empty and rely on env vars.
+        Self {
+            file_values: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        if let Ok(value) = env::var(key) {
+            return Some(value);
+        }
+        self.file_values.get(key).and_then(|v| v.as_str()).map(String::from)
+    }
+
+    pub fn get_secret(&self, key: &str) -> Option<String> {
+        self.get(key)
+    }
+
+    pub fn get_value(&self, key: &str) -> Option<Value> {
+        if let Ok(value) = env::var(key) {
+            return serde_json::from_str(&value).ok();
+        }
+        self.file_values.get(key).cloned()
+    }
+}
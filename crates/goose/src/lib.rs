@@ -0,0 +1,24 @@
+pub mod config;
+pub mod conversation;
+pub mod model;
+pub mod providers;
+
+/// Generates a `Default` impl for a provider that builds itself from env/config
+/// using its metadata's default model. Every provider that supports
+/// `from_env(ModelConfig) -> Result<Self>` uses this instead of hand-writing
+/// the same boilerplate.
+#[macro_export]
+macro_rules! impl_provider_default {
+    ($provider:ty) => {
+        impl Default for $provider {
+            fn default() -> Self {
+                let metadata = <$provider>::metadata();
+                let model = $crate::model::ModelConfig::new_or_fail(&metadata.default_model);
+                <$provider>::from_env(model).expect(concat!(
+                    stringify!($provider),
+                    "::from_env failed; is it configured?"
+                ))
+            }
+        }
+    };
+}
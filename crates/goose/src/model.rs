@@ -0,0 +1,43 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// The model a provider should target, plus the token-accounting goose needs
+/// to keep a conversation inside its context window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelConfig {
+    pub model_name: String,
+    pub context_limit: Option<usize>,
+    pub max_output_tokens: Option<usize>,
+    pub temperature: Option<f32>,
+}
+
+impl ModelConfig {
+    pub fn new(model_name: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            model_name: model_name.into(),
+            context_limit: None,
+            max_output_tokens: None,
+            temperature: None,
+        })
+    }
+
+    /// Convenience constructor for call sites (mostly tests) that know the
+    /// name is valid and don't want to propagate a `Result`.
+    pub fn new_or_fail(model_name: &str) -> Self {
+        Self::new(model_name).expect("model name should be valid")
+    }
+
+    pub fn with_context_limit(mut self, limit: Option<usize>) -> Self {
+        self.context_limit = limit;
+        self
+    }
+
+    pub fn with_max_output_tokens(mut self, max_output_tokens: Option<usize>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    pub fn context_limit(&self) -> usize {
+        self.context_limit.unwrap_or(128_000)
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::{Client, Proxy, RequestBuilder, Response};
+use serde_json::Value;
+
+/// How a request authenticates itself against the provider's API.
+pub enum AuthMethod {
+    BearerToken(String),
+    ApiKeyHeader { header: String, key: String },
+    None,
+}
+
+impl AuthMethod {
+    fn apply(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self {
+            AuthMethod::BearerToken(token) => builder.bearer_auth(token),
+            AuthMethod::ApiKeyHeader { header, key } => builder.header(header, key),
+            AuthMethod::None => builder,
+        }
+    }
+}
+
+/// Network settings that don't vary by request but do vary by deployment:
+/// routing through a corporate proxy, or bounding how long we'll wait to
+/// connect. Independent of [`AuthMethod`] since these apply at the
+/// transport layer rather than per-call.
+#[derive(Debug, Clone, Default)]
+pub struct ApiClientExtra {
+    pub proxy: Option<String>,
+    pub connect_timeout_secs: Option<u64>,
+}
+
+impl ApiClientExtra {
+    /// Read `{prefix}_PROXY` and `{prefix}_CONNECT_TIMEOUT` from config,
+    /// falling back to the standard `HTTPS_PROXY`/`ALL_PROXY` environment
+    /// variables for the proxy when unset. `prefix` is the provider's config
+    /// key prefix, e.g. `SWISS_AI` for `SWISS_AI_PROXY`.
+    pub fn from_config(prefix: &str) -> Self {
+        let config = crate::config::Config::global();
+
+        let proxy = config
+            .get_param(&format!("{prefix}_PROXY"))
+            .ok()
+            .or_else(|| std::env::var("HTTPS_PROXY").ok())
+            .or_else(|| std::env::var("ALL_PROXY").ok());
+
+        let connect_timeout_secs = config
+            .get_param(&format!("{prefix}_CONNECT_TIMEOUT"))
+            .ok()
+            .and_then(|v| v.parse().ok());
+
+        Self {
+            proxy,
+            connect_timeout_secs,
+        }
+    }
+}
+
+/// A thin wrapper around a [`reqwest::Client`] scoped to a single provider's
+/// host and auth method. Every provider builds one of these in `from_env` and
+/// uses it for every call instead of talking to `reqwest` directly, so
+/// auth and proxy/timeout settings live in one place.
+pub struct ApiClient {
+    host: String,
+    auth: AuthMethod,
+    client: Client,
+}
+
+/// A single in-flight request being assembled against an [`ApiClient`].
+pub struct ApiRequest {
+    builder: RequestBuilder,
+}
+
+impl ApiClient {
+    pub fn new(host: impl Into<String>, auth: AuthMethod) -> Result<Self> {
+        Self::new_with_extra(host, auth, ApiClientExtra::default())
+    }
+
+    pub fn new_with_extra(
+        host: impl Into<String>,
+        auth: AuthMethod,
+        extra: ApiClientExtra,
+    ) -> Result<Self> {
+        let mut builder = Client::builder();
+        if let Some(proxy_url) = &extra.proxy {
+            builder = builder.proxy(Proxy::all(proxy_url)?);
+        }
+        if let Some(secs) = extra.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        let client = builder.build()?;
+        Ok(Self {
+            host: host.into(),
+            auth,
+            client,
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.host.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+
+    /// Start a GET request against `path`, to be customized with `.header(...)`
+    /// before calling `.response_get()`.
+    pub fn request(&self, path: &str) -> ApiRequest {
+        let builder = self.auth.apply(self.client.get(self.url(path)));
+        ApiRequest { builder }
+    }
+
+    pub async fn response_post(&self, path: &str, payload: &Value) -> Result<Response> {
+        let builder = self.auth.apply(self.client.post(self.url(path)));
+        Ok(builder.json(payload).send().await?)
+    }
+
+    /// Like [`ApiClient::response_post`], but asks for a server-sent-events
+    /// body instead of a single JSON response.
+    pub async fn response_post_stream(&self, path: &str, payload: &Value) -> Result<Response> {
+        let builder = self.auth.apply(self.client.post(self.url(path)));
+        Ok(builder
+            .header("Accept", "text/event-stream")
+            .json(payload)
+            .send()
+            .await?)
+    }
+}
+
+impl ApiRequest {
+    pub fn header(mut self, key: &str, value: &str) -> Result<Self> {
+        self.builder = self.builder.header(key, value);
+        Ok(self)
+    }
+
+    pub async fn response_get(self) -> Result<Response> {
+        Ok(self.builder.send().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `cargo test` runs tests in the same binary concurrently by default;
+    // both tests below mutate the same process-global env vars, so they'd
+    // otherwise race each other.
+    static ENV_VAR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn test_extra_falls_back_to_https_proxy_env() {
+        let _guard = ENV_VAR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        std::env::remove_var("SWISS_AI_PROXY");
+        std::env::set_var("HTTPS_PROXY", "http://proxy.example.com:8080");
+
+        let extra = ApiClientExtra::from_config("SWISS_AI");
+        assert_eq!(extra.proxy, Some("http://proxy.example.com:8080".to_string()));
+
+        std::env::remove_var("HTTPS_PROXY");
+    }
+
+    #[test]
+    fn test_extra_prefers_explicit_config_over_env() {
+        let _guard = ENV_VAR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        std::env::set_var("SWISS_AI_PROXY", "http://explicit.example.com:9090");
+        std::env::set_var("HTTPS_PROXY", "http://proxy.example.com:8080");
+
+        let extra = ApiClientExtra::from_config("SWISS_AI");
+        assert_eq!(extra.proxy, Some("http://explicit.example.com:9090".to_string()));
+
+        std::env::remove_var("SWISS_AI_PROXY");
+        std::env::remove_var("HTTPS_PROXY");
+    }
+}
@@ -0,0 +1,396 @@
+use async_trait::async_trait;
+use rmcp::model::Tool;
+use serde::{Deserialize, Serialize};
+
+use super::errors::ProviderError;
+use crate::conversation::message::Message;
+use crate::model::ModelConfig;
+
+/// Config key under which users list models goose doesn't otherwise know
+/// about (or want to override the token limits of), e.g.:
+///
+/// ```yaml
+/// GOOSE_AVAILABLE_MODELS:
+///   - provider: swiss-ai
+///     name: llama-4-405b-instruct
+///     context_limit: 131072
+///     max_output_tokens: 8192
+/// ```
+pub const AVAILABLE_MODELS_CONFIG_KEY: &str = "GOOSE_AVAILABLE_MODELS";
+
+/// A single entry under [`AVAILABLE_MODELS_CONFIG_KEY`]: a model name for a
+/// given provider, plus the token limits goose should use for it. Lets users
+/// pick up a provider's newly released model - or correct the context window
+/// of one it already serves - without waiting for a crate update.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomModelConfig {
+    pub provider: String,
+    pub name: String,
+    pub context_limit: usize,
+    #[serde(default)]
+    pub max_output_tokens: Option<usize>,
+}
+
+fn configured_models() -> Vec<CustomModelConfig> {
+    crate::config::Config::global()
+        .get_param_as(AVAILABLE_MODELS_CONFIG_KEY)
+        .unwrap_or_default()
+}
+
+/// Guards tests that mutate process-global env vars, since `cargo test` runs
+/// tests in the same binary concurrently by default. Shared across modules
+/// (e.g. [`super::swiss_ai`]'s tests) wherever they touch the same env var
+/// as a test in this module, so unrelated test files don't race each other.
+#[cfg(test)]
+pub(crate) mod test_support {
+    pub(crate) static ENV_VAR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+}
+
+/// A single configuration value a provider needs from the user (an API key,
+/// a host override, ...). Rendered by the CLI/UI when a provider is configured.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigKey {
+    pub name: String,
+    pub required: bool,
+    pub secret: bool,
+    pub default: Option<String>,
+}
+
+impl ConfigKey {
+    pub fn new(name: &str, required: bool, secret: bool, default: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            required,
+            secret,
+            default: default.map(|d| d.to_string()),
+        }
+    }
+}
+
+/// A model a provider is known to support, along with the token accounting
+/// goose needs in order to manage context windows.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub context_limit: Option<usize>,
+    pub max_output_tokens: Option<usize>,
+}
+
+impl ModelInfo {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            context_limit: None,
+            max_output_tokens: None,
+        }
+    }
+}
+
+impl From<&str> for ModelInfo {
+    fn from(name: &str) -> Self {
+        ModelInfo::new(name)
+    }
+}
+
+/// Static description of a provider: what it's called, what models it knows
+/// about, and what config it needs to be constructed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderMetadata {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub default_model: String,
+    pub known_models: Vec<ModelInfo>,
+    pub model_doc_link: String,
+    pub config_keys: Vec<ConfigKey>,
+}
+
+impl ProviderMetadata {
+    pub fn new(
+        name: &str,
+        display_name: &str,
+        description: &str,
+        default_model: &str,
+        known_models: Vec<&str>,
+        model_doc_link: &str,
+        config_keys: Vec<ConfigKey>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            description: description.to_string(),
+            default_model: default_model.to_string(),
+            known_models: known_models.into_iter().map(ModelInfo::from).collect(),
+            model_doc_link: model_doc_link.to_string(),
+            config_keys,
+        }
+    }
+
+    /// Merge any [`CustomModelConfig`] entries configured for this provider
+    /// into `known_models`: an entry matching an existing model by name fills
+    /// in its token limits, anything new is appended. Called each time a
+    /// platform's metadata is built, so every caller of a provider's
+    /// `metadata()` sees the merged list.
+    pub fn with_custom_models(mut self) -> Self {
+        for custom in configured_models()
+            .into_iter()
+            .filter(|m| m.provider == self.name)
+        {
+            match self.known_models.iter_mut().find(|m| m.name == custom.name) {
+                Some(existing) => {
+                    existing.context_limit = Some(custom.context_limit);
+                    existing.max_output_tokens = custom.max_output_tokens;
+                }
+                None => self.known_models.push(ModelInfo {
+                    name: custom.name,
+                    context_limit: Some(custom.context_limit),
+                    max_output_tokens: custom.max_output_tokens,
+                }),
+            }
+        }
+        self
+    }
+
+    /// Turn the raw model names a provider's [`Provider::fetch_supported_models`]
+    /// reported into [`ModelInfo`]s, preferring a config-provided
+    /// [`CustomModelConfig`] (already folded into `known_models`) over
+    /// whatever this provider otherwise knows about that name.
+    pub fn resolve_fetched_models(&self, names: Vec<String>) -> Vec<ModelInfo> {
+        names
+            .into_iter()
+            .map(|name| {
+                self.known_models
+                    .iter()
+                    .find(|m| m.name == name)
+                    .cloned()
+                    .unwrap_or_else(|| ModelInfo::new(name))
+            })
+            .collect()
+    }
+
+    /// Resolve `model`'s token limits against this provider's `known_models`
+    /// (already merged with any config overrides), preferring whatever's on
+    /// file there over whatever the caller passed in - this is how a
+    /// provider picks up correct context-window accounting for a model it
+    /// has never seen hard-coded, as long as the user declared it in config.
+    pub fn resolve_model_config(&self, model: ModelConfig) -> ModelConfig {
+        match self.known_models.iter().find(|m| m.name == model.model_name) {
+            Some(info) => {
+                let context_limit = info.context_limit.or(model.context_limit);
+                let max_output_tokens = info.max_output_tokens.or(model.max_output_tokens);
+                model
+                    .with_context_limit(context_limit)
+                    .with_max_output_tokens(max_output_tokens)
+            }
+            None => model,
+        }
+    }
+}
+
+/// Token accounting for a single completion.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Usage {
+    pub input_tokens: Option<i32>,
+    pub output_tokens: Option<i32>,
+    pub total_tokens: Option<i32>,
+}
+
+impl Usage {
+    pub fn new(
+        input_tokens: Option<i32>,
+        output_tokens: Option<i32>,
+        total_tokens: Option<i32>,
+    ) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            total_tokens,
+        }
+    }
+}
+
+/// Usage tagged with the model that actually served the request, since a
+/// provider may resolve an alias (e.g. "latest") to a concrete model.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderUsage {
+    pub model: String,
+    pub usage: Usage,
+}
+
+impl ProviderUsage {
+    pub fn new(model: String, usage: Usage) -> Self {
+        Self { model, usage }
+    }
+}
+
+/// A single incremental update from a streamed completion.
+#[derive(Debug, Clone)]
+pub enum MessageStreamEvent {
+    /// A chunk of an in-progress message.
+    Partial(Message),
+    /// The final usage report, emitted once the stream is exhausted.
+    Usage(ProviderUsage),
+}
+
+/// A source of LLM completions. Implementors wrap a specific backend (OpenAI,
+/// Anthropic, a self-hosted endpoint, ...) behind a common request/response
+/// shape so the rest of goose never has to special-case a vendor.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Static information about this provider: display name, known models,
+    /// and the config it needs. Implemented as an associated function rather
+    /// than a method since it must be callable before a provider is built.
+    fn metadata() -> ProviderMetadata
+    where
+        Self: Sized;
+
+    /// The model configuration this instance was constructed with.
+    fn get_model_config(&self) -> ModelConfig;
+
+    /// Run a single completion, given the system prompt, conversation so far,
+    /// and the tools available to the model.
+    async fn complete(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<(Message, ProviderUsage), ProviderError>;
+
+    /// Like [`Provider::complete`], but yields incremental chunks as they
+    /// arrive instead of waiting for the full response. Providers that don't
+    /// support streaming can rely on this default, which just runs a regular
+    /// completion and emits it as a single event.
+    async fn stream(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<
+        std::pin::Pin<
+            Box<dyn futures::Stream<Item = Result<MessageStreamEvent, ProviderError>> + Send>,
+        >,
+        ProviderError,
+    > {
+        let (message, usage) = self.complete(system, messages, tools).await?;
+        let events = vec![Ok(MessageStreamEvent::Partial(message)), Ok(MessageStreamEvent::Usage(usage))];
+        Ok(Box::pin(futures::stream::iter(events)))
+    }
+
+    /// Ask the provider's API which models it currently serves, resolved
+    /// against this provider's `known_models` (see
+    /// [`ProviderMetadata::resolve_fetched_models`]) so a user-declared
+    /// custom model's token limits win over whatever the API itself reports.
+    /// Returns `Ok(None)` for providers that don't expose such an endpoint.
+    async fn fetch_supported_models(&self) -> Result<Option<Vec<ModelInfo>>, ProviderError> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_available_models<T>(json: &str, test: impl FnOnce() -> T) -> T {
+        let _guard = test_support::ENV_VAR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        std::env::set_var(AVAILABLE_MODELS_CONFIG_KEY, json);
+        let result = test();
+        std::env::remove_var(AVAILABLE_MODELS_CONFIG_KEY);
+        result
+    }
+
+    fn sample_metadata() -> ProviderMetadata {
+        ProviderMetadata::new(
+            "swiss-ai",
+            "Swiss AI Platform",
+            "Swiss AI Platform with Llama models",
+            "llama-3.3-70b-instruct",
+            vec!["llama-3.3-70b-instruct"],
+            "",
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_with_custom_models_fills_in_existing_model() {
+        with_available_models(
+            r#"[{"provider": "swiss-ai", "name": "llama-3.3-70b-instruct", "context_limit": 131072}]"#,
+            || {
+                let metadata = sample_metadata().with_custom_models();
+                let model = metadata
+                    .known_models
+                    .iter()
+                    .find(|m| m.name == "llama-3.3-70b-instruct")
+                    .unwrap();
+                assert_eq!(model.context_limit, Some(131072));
+            },
+        );
+    }
+
+    #[test]
+    fn test_with_custom_models_appends_unknown_model() {
+        with_available_models(
+            r#"[{"provider": "swiss-ai", "name": "llama-5-mega", "context_limit": 1000000, "max_output_tokens": 8192}]"#,
+            || {
+                let metadata = sample_metadata().with_custom_models();
+                let model = metadata
+                    .known_models
+                    .iter()
+                    .find(|m| m.name == "llama-5-mega")
+                    .expect("custom model should be appended");
+                assert_eq!(model.context_limit, Some(1000000));
+                assert_eq!(model.max_output_tokens, Some(8192));
+            },
+        );
+    }
+
+    #[test]
+    fn test_with_custom_models_ignores_other_providers() {
+        with_available_models(
+            r#"[{"provider": "other-provider", "name": "llama-3.3-70b-instruct", "context_limit": 1}]"#,
+            || {
+                let metadata = sample_metadata().with_custom_models();
+                let model = metadata
+                    .known_models
+                    .iter()
+                    .find(|m| m.name == "llama-3.3-70b-instruct")
+                    .unwrap();
+                assert_eq!(model.context_limit, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_resolve_model_config_applies_known_limits() {
+        with_available_models(
+            r#"[{"provider": "swiss-ai", "name": "llama-3.3-70b-instruct", "context_limit": 131072}]"#,
+            || {
+                let metadata = sample_metadata().with_custom_models();
+                let model = ModelConfig::new_or_fail("llama-3.3-70b-instruct");
+                let resolved = metadata.resolve_model_config(model);
+                assert_eq!(resolved.context_limit, Some(131072));
+            },
+        );
+    }
+
+    #[test]
+    fn test_resolve_fetched_models_prefers_custom_limits() {
+        with_available_models(
+            r#"[{"provider": "swiss-ai", "name": "llama-new", "context_limit": 65536}]"#,
+            || {
+                let metadata = sample_metadata().with_custom_models();
+                let resolved = metadata.resolve_fetched_models(vec![
+                    "llama-new".to_string(),
+                    "llama-unknown".to_string(),
+                ]);
+                assert_eq!(
+                    resolved.iter().find(|m| m.name == "llama-new").unwrap().context_limit,
+                    Some(65536)
+                );
+                assert_eq!(
+                    resolved.iter().find(|m| m.name == "llama-unknown").unwrap().context_limit,
+                    None
+                );
+            },
+        );
+    }
+}
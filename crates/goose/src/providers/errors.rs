@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// Errors that can occur while talking to a model provider.
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Request failed: {0}")]
+    RequestFailed(String),
+
+    #[error("Context length exceeded: {0}")]
+    ContextLengthExceeded(String),
+
+    #[error("Rate limit exceeded: {0}")]
+    RateLimitExceeded(String),
+
+    #[error("Server error: {0}")]
+    ServerError(String),
+
+    #[error("Usage error: {0}")]
+    UsageError(String),
+
+    #[error("Not implemented: {0}")]
+    NotImplemented(String),
+
+    #[error(transparent)]
+    ExecutionError(#[from] anyhow::Error),
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use rmcp::model::Tool;
+use serde_json::{json, Value};
+
+use super::super::base::Usage;
+use super::super::errors::ProviderError;
+use super::super::utils::ImageFormat;
+use crate::conversation::message::Message;
+use crate::model::ModelConfig;
+
+/// Build the JSON payload for an OpenAI-compatible `v1/chat/completions`
+/// request out of goose's internal conversation representation.
+pub fn create_request(
+    model: &ModelConfig,
+    system: &str,
+    messages: &[Message],
+    tools: &[Tool],
+    _image_format: &ImageFormat,
+) -> Result<Value> {
+    let mut request_messages = vec![json!({"role": "system", "content": system})];
+    request_messages.extend(messages.iter().map(|m| m.to_openai_value()));
+
+    let mut payload = json!({
+        "model": model.model_name,
+        "messages": request_messages,
+    });
+
+    if !tools.is_empty() {
+        payload["tools"] = json!(tools);
+    }
+
+    Ok(payload)
+}
+
+/// Parse the `usage` block of an OpenAI-compatible response into goose's
+/// internal [`Usage`] type.
+pub fn get_usage(usage: &Value) -> Usage {
+    Usage::new(
+        usage.get("prompt_tokens").and_then(|v| v.as_i64()).map(|v| v as i32),
+        usage.get("completion_tokens").and_then(|v| v.as_i64()).map(|v| v as i32),
+        usage.get("total_tokens").and_then(|v| v.as_i64()).map(|v| v as i32),
+    )
+}
+
+/// Turn the first choice of an OpenAI-compatible response into a goose
+/// [`Message`].
+pub fn response_to_message(response: &Value) -> Result<Message, ProviderError> {
+    let content = response
+        .get("choices")
+        .and_then(|c| c.get(0))
+        .and_then(|c| c.get("message"))
+        .and_then(|m| m.get("content"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+
+    Ok(Message::assistant(content))
+}
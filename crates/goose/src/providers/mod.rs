@@ -0,0 +1,38 @@
+pub mod api_client;
+pub mod base;
+pub mod errors;
+pub mod formats;
+pub mod openai_compatible;
+pub mod retry;
+pub mod swiss_ai;
+pub mod utils;
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+
+use base::{Provider, ProviderMetadata};
+use crate::model::ModelConfig;
+
+/// Build a live provider by name. Built-in providers (like `swiss-ai`) are
+/// matched directly; anything else is looked up among the user's configured
+/// [`openai_compatible::PlatformConfig`] entries.
+pub fn create(name: &str, model: ModelConfig) -> Result<Arc<dyn Provider>> {
+    if name == "swiss-ai" {
+        return Ok(Arc::new(swiss_ai::SwissAiProvider::from_env(model)?));
+    }
+
+    if let Some(provider) = openai_compatible::OpenAiCompatibleProvider::from_config(name, model)? {
+        return Ok(Arc::new(provider));
+    }
+
+    bail!("unknown provider: {name}")
+}
+
+/// Metadata for every provider goose knows about: the built-ins plus every
+/// OpenAI-compatible platform the user has defined in config.
+pub fn all_metadata() -> Vec<ProviderMetadata> {
+    let mut metadata = vec![swiss_ai::SwissAiProvider::metadata()];
+    metadata.extend(openai_compatible::OpenAiCompatibleProvider::configured_metadata());
+    metadata
+}
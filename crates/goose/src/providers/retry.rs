@@ -0,0 +1,45 @@
+use std::future::Future;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::errors::ProviderError;
+
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Retry policy shared by every provider: transient failures (rate limits,
+/// server errors) are retried with exponential backoff, everything else is
+/// returned to the caller immediately.
+#[async_trait]
+pub trait ProviderRetry {
+    async fn with_retry<F, Fut, T>(&self, mut request: F) -> Result<T, ProviderError>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, ProviderError>> + Send,
+        T: Send,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt < MAX_RETRIES && is_retryable(&err) => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+fn is_retryable(err: &ProviderError) -> bool {
+    matches!(
+        err,
+        ProviderError::RateLimitExceeded(_) | ProviderError::ServerError(_)
+    )
+}
+
+impl<T> ProviderRetry for T {}
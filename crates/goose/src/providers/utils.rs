@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+use std::pin::Pin;
+
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
+use reqwest::{Response, StatusCode};
+use serde_json::Value;
+
+use super::base::{MessageStreamEvent, ProviderUsage, Usage};
+use super::errors::ProviderError;
+use super::formats::openai::get_usage;
+use crate::conversation::message::{Message, ToolCall};
+use crate::model::ModelConfig;
+
+const SSE_DONE_SENTINEL: &str = "[DONE]";
+
+/// A tool call being reassembled from streamed deltas, keyed by the `index`
+/// an OpenAI-compatible endpoint tags each chunk with. `id`/`name` typically
+/// arrive once on the first delta for that index, while `arguments` is
+/// appended to a few characters at a time.
+#[derive(Debug, Clone, Default)]
+struct StreamedToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Find the byte offset of the first `\n\n` in `buf`, the boundary SSE uses
+/// between frames.
+fn find_frame_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(2).position(|w| w == b"\n\n")
+}
+
+/// How images embedded in a conversation should be encoded for a given
+/// provider's chat-completions payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    OpenAi,
+    Anthropic,
+}
+
+/// Pull the `model` field a provider's response reports actually served the
+/// request, or an empty string if the response has no `model` field.
+pub fn get_model(response: &Value) -> String {
+    response
+        .get("model")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Map a non-2xx OpenAI-compatible HTTP status (plus the body that came with
+/// it) to the matching [`ProviderError`]. Shared by [`handle_response_openai_compat`]
+/// and [`decode_openai_compat_stream`], since a streamed request can fail the
+/// same ways a non-streamed one can.
+fn classify_error_status(status: StatusCode, body: String) -> ProviderError {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ProviderError::Authentication(body),
+        StatusCode::TOO_MANY_REQUESTS => ProviderError::RateLimitExceeded(body),
+        status if status.is_server_error() => ProviderError::ServerError(body),
+        status => ProviderError::RequestFailed(format!("{status}: {body}")),
+    }
+}
+
+/// Turn a raw HTTP response from an OpenAI-compatible endpoint into parsed
+/// JSON, mapping common HTTP failure modes to the matching [`ProviderError`].
+pub async fn handle_response_openai_compat(response: Response) -> Result<Value, ProviderError> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| ProviderError::RequestFailed(e.to_string()))?;
+
+    if status != StatusCode::OK {
+        return Err(classify_error_status(status, body));
+    }
+
+    serde_json::from_str(&body)
+        .map_err(|e| ProviderError::RequestFailed(format!("Invalid JSON response: {e}")))
+}
+
+/// Decode an OpenAI-compatible server-sent-events body into a stream of
+/// [`MessageStreamEvent`]s: raw bytes are buffered until a blank line closes
+/// out a complete frame (so a multi-byte UTF-8 character split across two
+/// network chunks is never decoded until both halves have arrived), each
+/// frame's `data: ` prefix is stripped, the `[DONE]` sentinel ends the
+/// stream, and `choices[0].delta.content` is appended to a running message.
+/// Tool-call deltas are merged by their `index` the same way, since an
+/// OpenAI-compatible endpoint streams a tool call's `name`/`arguments` a few
+/// characters at a time rather than all at once; once the stream ends, the
+/// reassembled calls are attached to one final [`MessageStreamEvent::Partial`]
+/// so callers see them on the running message rather than just in a log.
+/// Usage is typically only present on the final frame, so it's emitted as a
+/// terminal event once the stream is exhausted rather than per-frame. A
+/// non-2xx response is never handed to the frame parser: its status is
+/// classified the same way [`handle_response_openai_compat`] classifies one,
+/// so an auth failure or rate limit surfaces as the matching [`ProviderError`]
+/// instead of an opaque frame-parse error or a silently empty completion.
+pub fn decode_openai_compat_stream(
+    response: Response,
+    model: ModelConfig,
+) -> Pin<Box<dyn Stream<Item = Result<MessageStreamEvent, ProviderError>> + Send>> {
+    Box::pin(try_stream! {
+        let status = response.status();
+        if status != StatusCode::OK {
+            let body = response
+                .text()
+                .await
+                .map_err(|e| ProviderError::RequestFailed(e.to_string()))?;
+            Err(classify_error_status(status, body))?;
+        }
+
+        let mut bytes_stream = response.bytes_stream();
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut content = String::new();
+        let mut tool_calls: BTreeMap<u64, StreamedToolCall> = BTreeMap::new();
+        let mut usage = Usage::default();
+
+        'read: while let Some(chunk) = bytes_stream.next().await {
+            let chunk = chunk.map_err(|e| ProviderError::RequestFailed(e.to_string()))?;
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(frame_end) = find_frame_end(&buffer) {
+                let frame_bytes: Vec<u8> = buffer.drain(..frame_end + 2).collect();
+                let frame = String::from_utf8_lossy(&frame_bytes);
+
+                let Some(data) = frame
+                    .lines()
+                    .find_map(|line| line.strip_prefix("data: ").or_else(|| line.strip_prefix("data:")))
+                else {
+                    continue;
+                };
+                let data = data.trim();
+                if data == SSE_DONE_SENTINEL {
+                    break 'read;
+                }
+
+                let event: Value = serde_json::from_str(data).map_err(|e| {
+                    ProviderError::RequestFailed(format!("Invalid SSE frame: {e}"))
+                })?;
+
+                if let Some(event_usage) = event.get("usage") {
+                    usage = get_usage(event_usage);
+                }
+
+                let Some(delta) = event
+                    .get("choices")
+                    .and_then(|c| c.get(0))
+                    .and_then(|c| c.get("delta"))
+                else {
+                    continue;
+                };
+
+                if let Some(text) = delta.get("content").and_then(|v| v.as_str()) {
+                    content.push_str(text);
+                    yield MessageStreamEvent::Partial(Message::assistant(content.clone()));
+                }
+
+                for tool_call in delta.get("tool_calls").and_then(|v| v.as_array()).into_iter().flatten() {
+                    let index = tool_call.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let entry = tool_calls.entry(index).or_default();
+
+                    if let Some(id) = tool_call.get("id").and_then(|v| v.as_str()) {
+                        entry.id = id.to_string();
+                    }
+                    if let Some(function) = tool_call.get("function") {
+                        if let Some(name) = function.get("name").and_then(|v| v.as_str()) {
+                            entry.name = name.to_string();
+                        }
+                        if let Some(args) = function.get("arguments").and_then(|v| v.as_str()) {
+                            entry.arguments.push_str(args);
+                        }
+                    }
+                }
+            }
+        }
+
+        if !tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = tool_calls
+                .into_values()
+                .map(|t| ToolCall { id: t.id, name: t.name, arguments: t.arguments })
+                .collect();
+            yield MessageStreamEvent::Partial(
+                Message::assistant(content.clone()).with_tool_calls(tool_calls),
+            );
+        }
+
+        yield MessageStreamEvent::Usage(ProviderUsage::new(model.model_name, usage));
+    })
+}
+
+/// Record the request/response pair and resulting usage on the current
+/// tracing span, so a single completion can be inspected end to end.
+pub fn emit_debug_trace(model: &ModelConfig, payload: &Value, response: &Value, usage: &Usage) {
+    tracing::Span::current().record("model_config", tracing::field::debug(model));
+    tracing::Span::current().record("input", tracing::field::debug(payload));
+    tracing::Span::current().record("output", tracing::field::debug(response));
+    tracing::Span::current().record("input_tokens", usage.input_tokens.unwrap_or_default());
+    tracing::Span::current().record("output_tokens", usage.output_tokens.unwrap_or_default());
+    tracing::Span::current().record("total_tokens", usage.total_tokens.unwrap_or_default());
+}
+
+/// Whether `pattern` - a model name that may contain `*` wildcards - matches
+/// `text`. Used to key [`load_model_patches`] entries by a glob like
+/// `"llama-*"` instead of one entry per exact model name.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                matches(&pattern[1..], text) || (!text.is_empty() && matches(pattern, &text[1..]))
+            }
+            (Some(p), Some(t)) if p == t => matches(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+    matches(pattern.as_bytes(), text.as_bytes())
+}
+
+/// Recursively merge `patch` into `payload`: an object in `patch` merges key
+/// by key into the matching object in `payload`, anything else (a scalar, an
+/// array, or a key present in one but not the other) overwrites wholesale,
+/// and an explicit JSON `null` deletes that key instead of setting it. This
+/// is how a [`load_model_patches`] entry gets applied on top of whatever
+/// `create_request` built.
+pub fn deep_merge_json(payload: &mut Value, patch: &Value) {
+    if let (Value::Object(payload_map), Value::Object(patch_map)) = (&mut *payload, patch) {
+        for (key, patch_value) in patch_map {
+            if patch_value.is_null() {
+                payload_map.remove(key);
+                continue;
+            }
+            match payload_map.get_mut(key) {
+                Some(existing) => deep_merge_json(existing, patch_value),
+                None => {
+                    payload_map.insert(key.clone(), patch_value.clone());
+                }
+            }
+        }
+    } else {
+        *payload = patch.clone();
+    }
+}
+
+/// Read the model-name-glob -> JSON-patch map configured for a provider
+/// under `{prefix}_MODEL_PATCHES`, e.g. `SWISS_AI_MODEL_PATCHES`. Lets a user
+/// adapt goose to a nonconforming model (drop `tools`, force a `stop`
+/// sequence, ...) without a crate change.
+pub fn load_model_patches(prefix: &str) -> BTreeMap<String, Value> {
+    crate::config::Config::global()
+        .get_param_as(&format!("{prefix}_MODEL_PATCHES"))
+        .unwrap_or_default()
+}
+
+/// Apply every patch in `patches` whose glob key matches `model_name` to
+/// `payload`. `patches` is a [`BTreeMap`], so when two glob patterns both
+/// match the same model with conflicting fields, the one that sorts later
+/// alphabetically by pattern wins (it's applied last and overwrites), not
+/// whichever was written first in config.
+pub fn apply_model_patches(payload: &mut Value, model_name: &str, patches: &BTreeMap<String, Value>) {
+    for (pattern, patch) in patches {
+        if glob_matches(pattern, model_name) {
+            deep_merge_json(payload, patch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Wrap `chunks` as the body of a fake 200 response, so
+    /// `decode_openai_compat_stream` sees them as successive network reads
+    /// with exactly the boundaries given, instead of one contiguous buffer.
+    fn sse_response(chunks: Vec<Vec<u8>>) -> Response {
+        let body = reqwest::Body::wrap_stream(futures::stream::iter(
+            chunks.into_iter().map(Ok::<_, std::io::Error>),
+        ));
+        let http_response = http::Response::builder().status(200).body(body).unwrap();
+        Response::from(http_response)
+    }
+
+    /// Serialize `value` as a single SSE `data: ` frame.
+    fn frame(value: Value) -> Vec<u8> {
+        format!("data: {value}\n\n").into_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_decode_stream_reassembles_split_utf8_and_tool_call_across_chunks() {
+        let frame_content = frame(json!({"choices": [{"delta": {"content": "café"}}]}));
+        // The tool-call's `arguments` is split mid-string across two frames,
+        // the same way an OpenAI-compatible endpoint streams it a few
+        // characters at a time.
+        let frame_tool_call_1 = frame(json!({"choices": [{"delta": {"tool_calls": [
+            {"index": 0, "id": "call_1", "function": {"name": "get_weather", "arguments": "{\"location\":\"NY"}}
+        ]}}]}));
+        let frame_tool_call_2 = frame(json!({"choices": [{"delta": {"tool_calls": [
+            {"index": 0, "function": {"arguments": "C\"}"}}
+        ]}}]}));
+        let frame_usage = frame(
+            json!({"usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}}),
+        );
+        let frame_done = b"data: [DONE]\n\n".to_vec();
+
+        let mut full = Vec::new();
+        full.extend_from_slice(&frame_content);
+        full.extend_from_slice(&frame_tool_call_1);
+        full.extend_from_slice(&frame_tool_call_2);
+        full.extend_from_slice(&frame_usage);
+        full.extend_from_slice(&frame_done);
+
+        // "café" - é is the two-byte UTF-8 sequence 0xC3 0xA9; split the
+        // network chunk boundary between those two bytes.
+        let utf8_split = frame_content
+            .windows(2)
+            .position(|w| w == [0xC3, 0xA9])
+            .expect("frame_content should contain the encoded é")
+            + 1;
+        // Also force a chunk boundary mid-way through the second tool-call
+        // frame, so a partial SSE frame has to be buffered across chunks too.
+        let tool_call_2_start = frame_content.len() + frame_tool_call_1.len();
+        let mid_tool_call_2 = tool_call_2_start + frame_tool_call_2.len() / 2;
+
+        let chunk_a = full[..utf8_split].to_vec();
+        let chunk_b = full[utf8_split..mid_tool_call_2].to_vec();
+        let chunk_c = full[mid_tool_call_2..].to_vec();
+
+        let response = sse_response(vec![chunk_a, chunk_b, chunk_c]);
+        let model = ModelConfig::new_or_fail("llama-3.3-70b-instruct");
+
+        let mut stream = decode_openai_compat_stream(response, model);
+        let mut events = Vec::new();
+        while let Some(event) = stream.next().await {
+            events.push(event.expect("well-formed stream should not error"));
+        }
+
+        let partials: Vec<&Message> = events
+            .iter()
+            .filter_map(|e| match e {
+                MessageStreamEvent::Partial(m) => Some(m),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(partials.first().unwrap().content, "café");
+
+        let final_message = partials.last().expect("stream should yield a final message");
+        assert_eq!(final_message.tool_calls.len(), 1);
+        assert_eq!(final_message.tool_calls[0].id, "call_1");
+        assert_eq!(final_message.tool_calls[0].name, "get_weather");
+        assert_eq!(
+            final_message.tool_calls[0].arguments,
+            "{\"location\":\"NYC\"}"
+        );
+
+        let usage_event = events.iter().find_map(|e| match e {
+            MessageStreamEvent::Usage(u) => Some(u),
+            _ => None,
+        });
+        assert_eq!(usage_event.unwrap().usage.total_tokens, Some(15));
+    }
+
+    #[tokio::test]
+    async fn test_decode_stream_maps_error_status_instead_of_parsing_body_as_sse() {
+        let body = reqwest::Body::from("{\"error\": \"invalid api key\"}");
+        let http_response = http::Response::builder().status(401).body(body).unwrap();
+        let response = Response::from(http_response);
+        let model = ModelConfig::new_or_fail("llama-3.3-70b-instruct");
+
+        let mut stream = decode_openai_compat_stream(response, model);
+        let event = stream
+            .next()
+            .await
+            .expect("stream should yield the classified error")
+            .expect_err("a 401 body should not be parsed as an SSE frame");
+
+        assert!(matches!(event, ProviderError::Authentication(_)));
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("llama-*", "llama-3.3-70b-instruct"));
+        assert!(glob_matches("*-instruct", "llama-3.3-70b-instruct"));
+        assert!(glob_matches("llama-3.3-70b-instruct", "llama-3.3-70b-instruct"));
+        assert!(!glob_matches("mistral-*", "llama-3.3-70b-instruct"));
+    }
+
+    #[test]
+    fn test_deep_merge_json_overwrites_scalars_and_merges_objects() {
+        let mut payload = json!({
+            "model": "llama-3.3-70b-instruct",
+            "temperature": 0.7,
+            "tools": ["a", "b"],
+            "response_format": {"type": "text"},
+        });
+        let patch = json!({
+            "temperature": null,
+            "tools": [],
+            "response_format": {"type": "json_object"},
+            "stop": ["<|end|>"],
+        });
+
+        deep_merge_json(&mut payload, &patch);
+
+        assert_eq!(payload["model"], json!("llama-3.3-70b-instruct"));
+        assert!(payload.get("temperature").is_none());
+        assert_eq!(payload["tools"], json!([]));
+        assert_eq!(payload["response_format"], json!({"type": "json_object"}));
+        assert_eq!(payload["stop"], json!(["<|end|>"]));
+    }
+
+    #[test]
+    fn test_apply_model_patches_overlapping_patterns_resolve_alphabetically() {
+        let mut patches = BTreeMap::new();
+        patches.insert("llama-*".to_string(), json!({"temperature": 0.1}));
+        patches.insert("*-instruct".to_string(), json!({"temperature": 0.9}));
+
+        let mut payload = json!({"temperature": 0.7});
+        apply_model_patches(&mut payload, "llama-3.3-70b-instruct", &patches);
+
+        // Both patterns match; `BTreeMap` iterates by key, and "llama-*"
+        // sorts after "*-instruct", so it's applied last and wins.
+        assert_eq!(payload["temperature"], json!(0.1));
+    }
+}